@@ -2,6 +2,74 @@ use std::io::{BufRead, Write};
 
 use thiserror::Error;
 
+/// Configuration for how a [`BfInterpreter`] behaves in situations the
+/// Brainfuck language itself leaves up to the implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BfConfig {
+    pub cell_overflow: CellOverflow,
+    pub eof_behavior: EofBehavior,
+    pub tape_config: TapeConfig,
+    /// When `true`, the parsed program is run through [`fold_instructions`]
+    /// before execution, folding runs of `+`/`-` and `>`/`<` into single
+    /// counted steps and recognizing `[-]`/`[+]` as a direct cell clear.
+    /// Defaults to `false` so the unoptimized, one-character-per-step path
+    /// stays available for debugging.
+    pub optimize: bool,
+}
+
+/// How `+`/`-` should behave when a cell would overflow or underflow its
+/// `u8` range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CellOverflow {
+    /// 255 + 1 wraps to 0 and 0 - 1 wraps to 255. This is what most
+    /// Brainfuck programs assume, so it is the default.
+    #[default]
+    Wrapping,
+    /// 255 + 1 stays at 255 and 0 - 1 stays at 0.
+    Saturating,
+    /// 255 + 1 and 0 - 1 return [`BfError::CellOverflow`].
+    Error,
+}
+
+/// What the `,` instruction should do once the input stream is exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EofBehavior {
+    /// Returns [`BfError::LackOfInput`], matching the interpreter's
+    /// original strict behavior.
+    #[default]
+    Error,
+    /// Leaves the current cell's value untouched.
+    KeepUnchanged,
+    /// Writes 0 into the current cell.
+    Zero,
+    /// Writes 255 (all bits set) into the current cell.
+    AllOnes,
+}
+
+/// Configuration for the tape's size and how `>`/`<` behave at its ends.
+///
+/// With `size: None` (the default), the tape starts at one cell and grows
+/// to the right on demand, as it always has; `<` at cell 0 is then always
+/// an error. Setting `size` to `Some(n)` fixes the tape at `n` cells and
+/// switches `>`/`<` to the configured [`TapePointerPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TapeConfig {
+    pub size: Option<usize>,
+    pub pointer_policy: TapePointerPolicy,
+}
+
+/// How `>`/`<` behave at the ends of a fixed-size tape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TapePointerPolicy {
+    /// Moving past either end returns [`BfError::SeekOverRightmost`] or
+    /// [`BfError::SeekOverLeftmost`].
+    #[default]
+    Bounded,
+    /// Moving right past the last cell wraps to cell 0, and moving left
+    /// past cell 0 wraps to the last cell.
+    Wrapping,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BfInterpreter<I, O> {
     instructions: Vec<BfInstruction>,
@@ -11,6 +79,7 @@ pub struct BfInterpreter<I, O> {
     tape_pointer: usize,
     input: I,
     output: O,
+    config: BfConfig,
 }
 
 impl<I, O> BfInterpreter<I, O>
@@ -19,47 +88,35 @@ where
     O: Write,
 {
     pub fn new(source: &str, input: I, output: O) -> Result<Self, BfError> {
+        Self::with_config(source, input, output, BfConfig::default())
+    }
+
+    pub fn with_config(
+        source: &str,
+        input: I,
+        output: O,
+        config: BfConfig,
+    ) -> Result<Self, BfError> {
+        if config.tape_config.size == Some(0) {
+            return Err(BfError::EmptyTape);
+        }
         let mut instructions = vec![];
-        let mut loop_stack = vec![];
         let mut jump_memo = vec![];
-        for code in source.chars() {
-            match code {
-                '>' => instructions.push(BfInstruction::GoRight),
-                '<' => instructions.push(BfInstruction::GoLeft),
-                '+' => instructions.push(BfInstruction::Increment),
-                '-' => instructions.push(BfInstruction::Decrement),
-                ',' => instructions.push(BfInstruction::GetInput),
-                '.' => instructions.push(BfInstruction::PutOutput),
-                '[' => {
-                    loop_stack.push(instructions.len());
-                    instructions.push(BfInstruction::LoopStart);
-                }
-                ']' => {
-                    let ending = instructions.len();
-                    let Some(beginning) = loop_stack.pop() else {
-                        return Err(BfError::LoopNotStarted);
-                    };
-                    if jump_memo.len() < ending {
-                        jump_memo.resize(ending + 1, 0);
-                    }
-                    jump_memo[beginning] = ending;
-                    jump_memo[ending] = beginning;
-                    instructions.push(BfInstruction::LoopEnd);
-                }
-                _ => {}
-            }
-        }
-        if !loop_stack.is_empty() {
-            return Err(BfError::LoopNotEnded);
-        }
+        parse_source(source, &mut instructions, &mut jump_memo)?;
+        let (instructions, jump_memo) = if config.optimize {
+            fold_instructions(&instructions, &jump_memo, &config)
+        } else {
+            (instructions, jump_memo)
+        };
         Ok(Self {
             instructions,
             instruction_pointer: 0,
             jump_memo,
-            tape: vec![0],
+            tape: initial_tape(&config),
             tape_pointer: 0,
             input,
             output,
+            config,
         })
     }
 
@@ -79,33 +136,81 @@ where
         &self.instructions[self.instruction_pointer]
     }
 
-    pub fn step(&mut self) -> Result<(), BfError> {
-        match self.current_instruction() {
-            BfInstruction::GoRight => {
-                self.tape_pointer += 1;
-                if self.tape_pointer >= self.tape.len() {
-                    self.tape.push(0);
-                }
+    fn move_pointer(&mut self, delta: isize) -> Result<(), BfError> {
+        match self.config.tape_config.size {
+            Some(size) => {
+                let size = size as isize;
+                let moved = self.tape_pointer as isize + delta;
+                self.tape_pointer = match self.config.tape_config.pointer_policy {
+                    TapePointerPolicy::Bounded => {
+                        if moved < 0 {
+                            return Err(BfError::SeekOverLeftmost);
+                        }
+                        if moved >= size {
+                            return Err(BfError::SeekOverRightmost);
+                        }
+                        moved as usize
+                    }
+                    TapePointerPolicy::Wrapping => moved.rem_euclid(size) as usize,
+                };
             }
-            BfInstruction::GoLeft => {
-                if self.tape_pointer == 0 {
+            None => {
+                let moved = self.tape_pointer as isize + delta;
+                if moved < 0 {
                     return Err(BfError::SeekOverLeftmost);
                 }
-                self.tape_pointer -= 1;
+                self.tape_pointer = moved as usize;
+                while self.tape_pointer >= self.tape.len() {
+                    self.tape.push(0);
+                }
             }
-            BfInstruction::Increment => {
-                *self.head_value_mut() += 1;
+        }
+        Ok(())
+    }
+
+    fn add_to_head(&mut self, delta: i8) -> Result<(), BfError> {
+        match self.config.cell_overflow {
+            CellOverflow::Wrapping => {
+                *self.head_value_mut() = self.head_value().wrapping_add_signed(delta);
             }
-            BfInstruction::Decrement => {
-                *self.head_value_mut() -= 1;
+            CellOverflow::Saturating => {
+                let value = self.head_value() as i16 + delta as i16;
+                *self.head_value_mut() = value.clamp(0, u8::MAX as i16) as u8;
             }
+            CellOverflow::Error => {
+                let value = self.head_value() as i16 + delta as i16;
+                if !(0..=u8::MAX as i16).contains(&value) {
+                    return Err(BfError::CellOverflow);
+                }
+                *self.head_value_mut() = value as u8;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn step(&mut self) -> Result<(), BfError> {
+        match *self.current_instruction() {
+            BfInstruction::GoRight => self.move_pointer(1)?,
+            BfInstruction::GoLeft => self.move_pointer(-1)?,
+            BfInstruction::Move(delta) => self.move_pointer(delta)?,
+            BfInstruction::Increment => self.add_to_head(1)?,
+            BfInstruction::Decrement => self.add_to_head(-1)?,
+            BfInstruction::Add(delta) => self.add_to_head(delta)?,
+            BfInstruction::SetZero => *self.head_value_mut() = 0,
+            BfInstruction::Breakpoint => {}
             BfInstruction::GetInput => {
                 let buf = self.input.fill_buf()?;
                 if buf.is_empty() {
-                    return Err(BfError::LackOfInput);
+                    match self.config.eof_behavior {
+                        EofBehavior::Error => return Err(BfError::LackOfInput),
+                        EofBehavior::KeepUnchanged => {}
+                        EofBehavior::Zero => *self.head_value_mut() = 0,
+                        EofBehavior::AllOnes => *self.head_value_mut() = 255,
+                    }
+                } else {
+                    *self.head_value_mut() = buf[0];
+                    self.input.consume(1);
                 }
-                *self.head_value_mut() = buf[0];
-                self.input.consume(1);
             }
             BfInstruction::PutOutput => {
                 self.output.write(&[self.head_value()])?;
@@ -126,11 +231,160 @@ where
     }
 
     pub fn execute(mut self) -> Result<(), BfError> {
+        self.run_to_end()
+    }
+
+    /// Parses `source` and appends the resulting instructions to this
+    /// interpreter's program, re-resolving bracket matching among the newly
+    /// appended instructions. The tape, tape pointer, and instruction
+    /// pointer are left untouched, so a program that previously reached the
+    /// end of its instructions will resume by running the appended code.
+    /// Loops must be balanced within `source` itself; a loop opened in an
+    /// earlier call cannot be closed by a later one.
+    pub fn append_source(&mut self, source: &str) -> Result<(), BfError> {
+        parse_source(source, &mut self.instructions, &mut self.jump_memo)
+    }
+
+    /// Runs instructions from the current `instruction_pointer` until
+    /// [`Self::is_end`] becomes true, without consuming `self`. Combined
+    /// with [`Self::append_source`], this lets a REPL execute only the
+    /// instructions from the most recently appended snippet.
+    pub fn run_to_end(&mut self) -> Result<(), BfError> {
         while !self.is_end() {
             self.step()?;
         }
         Ok(())
     }
+
+    /// Returns the tape contents as parsed so far.
+    pub fn tape(&self) -> &[u8] {
+        &self.tape
+    }
+
+    /// Returns the current position of the tape pointer.
+    pub fn tape_pointer(&self) -> usize {
+        self.tape_pointer
+    }
+
+    /// Grants direct access to the input reader, e.g. so a REPL can read
+    /// further lines of user input without locking a second handle to the
+    /// same stream.
+    pub fn input_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Clears all parsed instructions and resets the tape and every
+    /// pointer to their initial state, keeping the configured input,
+    /// output, and [`BfConfig`].
+    pub fn reset(&mut self) {
+        self.instructions.clear();
+        self.jump_memo.clear();
+        self.instruction_pointer = 0;
+        self.tape = initial_tape(&self.config);
+        self.tape_pointer = 0;
+    }
+
+    /// Returns the current instruction pointer, for debuggers that want to
+    /// report where execution stopped.
+    pub fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    /// Steps until a [`BfInstruction::Breakpoint`] is encountered or the
+    /// program ends, returning which one stopped it. Breakpoints are
+    /// consumed like any other instruction, so calling this again resumes
+    /// past the one it just stopped at.
+    pub fn run_until_breakpoint(&mut self) -> Result<DebugStop, BfError> {
+        while !self.is_end() {
+            let hit_breakpoint = matches!(self.current_instruction(), BfInstruction::Breakpoint);
+            self.step()?;
+            if hit_breakpoint {
+                return Ok(DebugStop::Breakpoint);
+            }
+        }
+        Ok(DebugStop::End)
+    }
+
+    /// Returns a window of the tape spanning up to `radius` cells on either
+    /// side of `tape_pointer`, clamped to the tape's bounds, along with the
+    /// index of `tape_pointer` within the returned slice.
+    pub fn tape_window(&self, radius: usize) -> (&[u8], usize) {
+        let start = self.tape_pointer.saturating_sub(radius);
+        let end = (self.tape_pointer + radius + 1).min(self.tape.len());
+        (&self.tape[start..end], self.tape_pointer - start)
+    }
+
+    /// Returns a window of the instruction stream spanning up to `radius`
+    /// instructions on either side of `instruction_pointer`, clamped to the
+    /// program's bounds, along with the index of `instruction_pointer`
+    /// within the returned slice.
+    pub fn instruction_window(&self, radius: usize) -> (&[BfInstruction], usize) {
+        let start = self.instruction_pointer.saturating_sub(radius);
+        let end = (self.instruction_pointer + radius + 1).min(self.instructions.len());
+        (&self.instructions[start..end], self.instruction_pointer - start)
+    }
+}
+
+/// Why [`BfInterpreter::run_until_breakpoint`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugStop {
+    /// Stopped right after executing a [`BfInstruction::Breakpoint`].
+    Breakpoint,
+    /// Ran to the end of the instruction stream.
+    End,
+}
+
+fn initial_tape(config: &BfConfig) -> Vec<u8> {
+    match config.tape_config.size {
+        Some(size) => vec![0; size],
+        None => vec![0],
+    }
+}
+
+/// Parses `source` into [`BfInstruction`]s, pushing them onto `instructions`
+/// and recording bracket-matching jump targets into `jump_memo`. Shared by
+/// [`BfInterpreter::with_config`] and [`BfInterpreter::append_source`] so the
+/// character-to-instruction mapping can't drift between the two call sites.
+/// Loops must be balanced within a single call to this function; a loop
+/// opened by an earlier call cannot be closed by a later one.
+fn parse_source(
+    source: &str,
+    instructions: &mut Vec<BfInstruction>,
+    jump_memo: &mut Vec<usize>,
+) -> Result<(), BfError> {
+    let mut loop_stack = vec![];
+    for code in source.chars() {
+        match code {
+            '>' => instructions.push(BfInstruction::GoRight),
+            '<' => instructions.push(BfInstruction::GoLeft),
+            '+' => instructions.push(BfInstruction::Increment),
+            '-' => instructions.push(BfInstruction::Decrement),
+            ',' => instructions.push(BfInstruction::GetInput),
+            '.' => instructions.push(BfInstruction::PutOutput),
+            '#' => instructions.push(BfInstruction::Breakpoint),
+            '[' => {
+                loop_stack.push(instructions.len());
+                instructions.push(BfInstruction::LoopStart);
+            }
+            ']' => {
+                let ending = instructions.len();
+                let Some(beginning) = loop_stack.pop() else {
+                    return Err(BfError::LoopNotStarted);
+                };
+                if jump_memo.len() < ending {
+                    jump_memo.resize(ending + 1, 0);
+                }
+                jump_memo[beginning] = ending;
+                jump_memo[ending] = beginning;
+                instructions.push(BfInstruction::LoopEnd);
+            }
+            _ => {}
+        }
+    }
+    if !loop_stack.is_empty() {
+        return Err(BfError::LoopNotEnded);
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -143,6 +397,127 @@ pub enum BfInstruction {
     PutOutput,
     LoopStart,
     LoopEnd,
+    /// Moves the tape pointer by a signed amount in one step. Produced by
+    /// [`fold_instructions`] from a run of `>`/`<`.
+    Move(isize),
+    /// Adds a signed amount to the current cell in one step, wrapping,
+    /// saturating, or erroring per the configured [`CellOverflow`] exactly
+    /// like [`BfInstruction::Increment`]/[`BfInstruction::Decrement`].
+    /// Produced by [`fold_instructions`] from a run of `+`/`-`.
+    Add(i8),
+    /// Sets the current cell to 0 directly. Produced by
+    /// [`fold_instructions`] from the `[-]`/`[+]` idiom.
+    SetZero,
+    /// The conventional `#` breakpoint character. A no-op during normal
+    /// execution; [`BfInterpreter::run_until_breakpoint`] pauses here.
+    Breakpoint,
+}
+
+/// Folds a parsed instruction stream into a denser, faster-to-run form:
+/// runs of `+`/`-` become a single [`BfInstruction::Add`], runs of `>`/`<`
+/// become a single [`BfInstruction::Move`], and a loop whose entire body is
+/// one `+` or `-` (the `[-]`/`[+]` idiom) becomes [`BfInstruction::SetZero`].
+/// `jump_memo` is rebuilt to match the folded indices.
+///
+/// Folding a run into one counted step skips whatever boundary the
+/// unfolded instructions would have hit partway through, so each kind of
+/// run is only folded when `config` guarantees that boundary can't differ
+/// from running the run unfolded:
+/// - `+`/`-` runs and the `[-]`/`[+]` idiom only fold under
+///   [`CellOverflow::Wrapping`], since `Saturating` and `Error` both
+///   depend on the cell's value partway through the run.
+/// - `>`/`<` runs only fold on a fixed-size, wrapping tape, since modular
+///   arithmetic is associative; an unbounded tape or a bounded one can
+///   error partway through even if the net displacement is in range.
+///
+/// Runs that aren't safe to fold under the active `config` are copied
+/// through unchanged, one instruction at a time.
+fn fold_instructions(
+    instructions: &[BfInstruction],
+    jump_memo: &[usize],
+    config: &BfConfig,
+) -> (Vec<BfInstruction>, Vec<usize>) {
+    let fold_cell_ops = config.cell_overflow == CellOverflow::Wrapping;
+    let fold_moves = matches!(
+        config.tape_config,
+        TapeConfig {
+            size: Some(_),
+            pointer_policy: TapePointerPolicy::Wrapping,
+        }
+    );
+    let mut folded = vec![];
+    let mut orig_to_folded = vec![usize::MAX; instructions.len()];
+    let mut i = 0;
+    while i < instructions.len() {
+        let start = i;
+        match instructions[i] {
+            BfInstruction::LoopStart
+                if fold_cell_ops
+                    && jump_memo.get(i).copied() == Some(i + 2)
+                    && matches!(
+                        instructions.get(i + 1).copied(),
+                        Some(BfInstruction::Increment | BfInstruction::Decrement)
+                    ) =>
+            {
+                orig_to_folded[start] = folded.len();
+                folded.push(BfInstruction::SetZero);
+                i += 3;
+            }
+            BfInstruction::Increment | BfInstruction::Decrement if fold_cell_ops => {
+                let mut delta: i32 = 0;
+                while let Some(BfInstruction::Increment | BfInstruction::Decrement) =
+                    instructions.get(i).copied()
+                {
+                    delta += if instructions[i] == BfInstruction::Increment {
+                        1
+                    } else {
+                        -1
+                    };
+                    i += 1;
+                }
+                orig_to_folded[start] = folded.len();
+                if delta != 0 {
+                    folded.push(BfInstruction::Add(delta as u8 as i8));
+                }
+            }
+            BfInstruction::GoRight | BfInstruction::GoLeft if fold_moves => {
+                let mut delta: isize = 0;
+                while let Some(BfInstruction::GoRight | BfInstruction::GoLeft) =
+                    instructions.get(i).copied()
+                {
+                    delta += if instructions[i] == BfInstruction::GoRight {
+                        1
+                    } else {
+                        -1
+                    };
+                    i += 1;
+                }
+                orig_to_folded[start] = folded.len();
+                if delta != 0 {
+                    folded.push(BfInstruction::Move(delta));
+                }
+            }
+            other => {
+                orig_to_folded[start] = folded.len();
+                folded.push(other);
+                i += 1;
+            }
+        }
+    }
+
+    let mut new_jump_memo = vec![0; folded.len()];
+    for (orig_idx, &folded_idx) in orig_to_folded.iter().enumerate() {
+        if folded_idx == usize::MAX {
+            continue;
+        }
+        if matches!(
+            folded[folded_idx],
+            BfInstruction::LoopStart | BfInstruction::LoopEnd
+        ) {
+            new_jump_memo[folded_idx] = orig_to_folded[jump_memo[orig_idx]];
+        }
+    }
+    (folded, new_jump_memo)
 }
 
 #[derive(Debug, Error)]
@@ -153,8 +528,14 @@ pub enum BfError {
     LoopNotEnded,
     #[error("cannot seek over leftmost of tape")]
     SeekOverLeftmost,
+    #[error("cannot seek over rightmost of tape")]
+    SeekOverRightmost,
     #[error("lack of input")]
     LackOfInput,
+    #[error("cell overflowed")]
+    CellOverflow,
+    #[error("tape size must be at least 1 cell")]
+    EmptyTape,
     #[error("input read error")]
     ReadError(#[from] std::io::Error),
 }
@@ -228,3 +609,560 @@ fn test_not_closing_loop() -> anyhow::Result<()> {
     assert!(matches!(err, BfError::LoopNotEnded));
     Ok(())
 }
+
+#[test]
+fn test_overflow_wrapping() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        &"+".repeat(256),
+        input,
+        &mut output,
+        BfConfig {
+            cell_overflow: CellOverflow::Wrapping,
+            ..BfConfig::default()
+        },
+    )?;
+    for _ in 0..256 {
+        interpreter.step()?;
+    }
+    assert_eq!(interpreter.head_value(), 0);
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        "-",
+        input,
+        &mut output,
+        BfConfig {
+            cell_overflow: CellOverflow::Wrapping,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.step()?;
+    assert_eq!(interpreter.head_value(), 255);
+    Ok(())
+}
+
+#[test]
+fn test_overflow_saturating() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        &"+".repeat(300),
+        input,
+        &mut output,
+        BfConfig {
+            cell_overflow: CellOverflow::Saturating,
+            ..BfConfig::default()
+        },
+    )?;
+    for _ in 0..300 {
+        interpreter.step()?;
+    }
+    assert_eq!(interpreter.head_value(), 255);
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        "-",
+        input,
+        &mut output,
+        BfConfig {
+            cell_overflow: CellOverflow::Saturating,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.step()?;
+    assert_eq!(interpreter.head_value(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_overflow_error() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        "-",
+        input,
+        &mut output,
+        BfConfig {
+            cell_overflow: CellOverflow::Error,
+            ..BfConfig::default()
+        },
+    )?;
+    let err = interpreter.step().expect_err("must occur cell overflow");
+    assert!(matches!(err, BfError::CellOverflow));
+    Ok(())
+}
+
+#[test]
+fn test_append_source_preserves_tape() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::new("+++", input, &mut output)?;
+    interpreter.run_to_end()?;
+    assert_eq!(interpreter.head_value(), 3);
+
+    interpreter.append_source("++.")?;
+    interpreter.run_to_end()?;
+    assert_eq!(interpreter.head_value(), 5);
+    assert_eq!(output, [5]);
+    Ok(())
+}
+
+#[test]
+fn test_append_source_unmatched_loop() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::new("", input, &mut output)?;
+    let err = interpreter
+        .append_source("[")
+        .expect_err("must occur syntax error");
+    assert!(matches!(err, BfError::LoopNotEnded));
+    Ok(())
+}
+
+#[test]
+fn test_reset_clears_tape_and_instructions() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::new("+++", input, &mut output)?;
+    interpreter.run_to_end()?;
+    interpreter.reset();
+    assert_eq!(interpreter.tape(), &[0]);
+    assert_eq!(interpreter.tape_pointer(), 0);
+    assert!(interpreter.is_end());
+
+    interpreter.append_source("+.")?;
+    interpreter.run_to_end()?;
+    assert_eq!(output, [1]);
+    Ok(())
+}
+
+#[test]
+fn test_echo_terminates_on_eof_with_zero() -> anyhow::Result<()> {
+    // Same echo program as `test_echo`, but the input has no trailing 0
+    // sentinel: the loop must stop because EOF is read as 0, not because
+    // the stream contained one.
+    let input = std::io::BufReader::new(&[1, 4, 2, 3, 5, 2, 3][..]);
+    let mut output = vec![];
+    let interpreter = BfInterpreter::with_config(
+        ",[.,]",
+        input,
+        &mut output,
+        BfConfig {
+            eof_behavior: EofBehavior::Zero,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.execute()?;
+    assert_eq!(output, [1, 4, 2, 3, 5, 2, 3]);
+    Ok(())
+}
+
+#[test]
+fn test_get_input_keeps_cell_unchanged_on_eof() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[5][..]);
+    let mut output = vec![];
+    let interpreter = BfInterpreter::with_config(
+        ",,.",
+        input,
+        &mut output,
+        BfConfig {
+            eof_behavior: EofBehavior::KeepUnchanged,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.execute()?;
+    assert_eq!(output, [5]);
+    Ok(())
+}
+
+#[test]
+fn test_get_input_writes_zero_on_eof() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[5][..]);
+    let mut output = vec![];
+    let interpreter = BfInterpreter::with_config(
+        ",,.",
+        input,
+        &mut output,
+        BfConfig {
+            eof_behavior: EofBehavior::Zero,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.execute()?;
+    assert_eq!(output, [0]);
+    Ok(())
+}
+
+#[test]
+fn test_get_input_writes_all_ones_on_eof() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[5][..]);
+    let mut output = vec![];
+    let interpreter = BfInterpreter::with_config(
+        ",,.",
+        input,
+        &mut output,
+        BfConfig {
+            eof_behavior: EofBehavior::AllOnes,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.execute()?;
+    assert_eq!(output, [255]);
+    Ok(())
+}
+
+#[test]
+fn test_get_input_errors_on_eof_by_default() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::new(",", input, &mut output)?;
+    let err = interpreter.step().expect_err("must occur lack of input");
+    assert!(matches!(err, BfError::LackOfInput));
+    Ok(())
+}
+
+#[test]
+fn test_bounded_tape_errors_at_both_ends() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        ">>",
+        input,
+        &mut output,
+        BfConfig {
+            tape_config: TapeConfig {
+                size: Some(2),
+                pointer_policy: TapePointerPolicy::Bounded,
+            },
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.step()?;
+    assert_eq!(interpreter.tape_pointer(), 1);
+    let err = interpreter
+        .step()
+        .expect_err("must occur seek over rightmost");
+    assert!(matches!(err, BfError::SeekOverRightmost));
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        "<",
+        input,
+        &mut output,
+        BfConfig {
+            tape_config: TapeConfig {
+                size: Some(2),
+                pointer_policy: TapePointerPolicy::Bounded,
+            },
+            ..BfConfig::default()
+        },
+    )?;
+    let err = interpreter.step().expect_err("must occur seek over leftmost");
+    assert!(matches!(err, BfError::SeekOverLeftmost));
+    Ok(())
+}
+
+#[test]
+fn test_wrapping_tape_wraps_at_both_ends() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        ">><",
+        input,
+        &mut output,
+        BfConfig {
+            tape_config: TapeConfig {
+                size: Some(2),
+                pointer_policy: TapePointerPolicy::Wrapping,
+            },
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.step()?;
+    assert_eq!(interpreter.tape_pointer(), 1);
+    interpreter.step()?;
+    assert_eq!(interpreter.tape_pointer(), 0);
+    interpreter.step()?;
+    assert_eq!(interpreter.tape_pointer(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_optimized_hello_world_matches_unoptimized() -> anyhow::Result<()> {
+    let source = "++++++++++[>+++++++>++++++++++>+++>++++<
+<<<-]>++.>+.+++++++..+++.>>++++.<++.<+++
++++++.--------.+++.------.--------.>+.";
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut optimized_output = vec![];
+    let interpreter = BfInterpreter::with_config(
+        source,
+        input,
+        &mut optimized_output,
+        BfConfig {
+            optimize: true,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.execute()?;
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut unoptimized_output = vec![];
+    let interpreter = BfInterpreter::new(source, input, &mut unoptimized_output)?;
+    interpreter.execute()?;
+
+    assert_eq!(optimized_output, unoptimized_output);
+    assert_eq!(optimized_output, b"Hello, world!");
+    Ok(())
+}
+
+#[test]
+fn test_optimized_sum_n_matches_unoptimized() -> anyhow::Result<()> {
+    let source = ",[[->>+>+<<<]>>>[-<<<+>>>]<[-<+>]<<-]>.";
+
+    let input = std::io::BufReader::new(&[3][..]);
+    let mut optimized_output = vec![];
+    let interpreter = BfInterpreter::with_config(
+        source,
+        input,
+        &mut optimized_output,
+        BfConfig {
+            optimize: true,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.execute()?;
+
+    let input = std::io::BufReader::new(&[3][..]);
+    let mut unoptimized_output = vec![];
+    let interpreter = BfInterpreter::new(source, input, &mut unoptimized_output)?;
+    interpreter.execute()?;
+
+    assert_eq!(optimized_output, unoptimized_output);
+    assert_eq!(optimized_output, [6]);
+    Ok(())
+}
+
+#[test]
+fn test_optimized_recognizes_set_zero_idiom() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let interpreter = BfInterpreter::with_config(
+        "+++++[-].",
+        input,
+        &mut output,
+        BfConfig {
+            optimize: true,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.execute()?;
+    assert_eq!(output, [0]);
+    Ok(())
+}
+
+#[test]
+fn test_optimized_matches_unoptimized_seek_error_on_unbounded_tape() -> anyhow::Result<()> {
+    // A net-zero `<>` run must still hit `SeekOverLeftmost` on the `<`,
+    // even though the folded `Move(0)` delta would otherwise be a no-op.
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        "<>",
+        input,
+        &mut output,
+        BfConfig {
+            optimize: true,
+            ..BfConfig::default()
+        },
+    )?;
+    let err = interpreter
+        .run_to_end()
+        .expect_err("must occur seek over leftmost");
+    assert!(matches!(err, BfError::SeekOverLeftmost));
+    Ok(())
+}
+
+#[test]
+fn test_optimized_matches_unoptimized_saturating_overflow() -> anyhow::Result<()> {
+    // Folding "+" x300 into one `Add` must not let the sum wrap past 255
+    // internally; it has to saturate exactly like the unfolded run does.
+    let source = "+".repeat(300);
+    let config = BfConfig {
+        cell_overflow: CellOverflow::Saturating,
+        optimize: true,
+        ..BfConfig::default()
+    };
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut optimized_output = vec![];
+    let mut interpreter =
+        BfInterpreter::with_config(&source, input, &mut optimized_output, config)?;
+    interpreter.run_to_end()?;
+    assert_eq!(interpreter.head_value(), 255);
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut unoptimized_output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        &source,
+        input,
+        &mut unoptimized_output,
+        BfConfig {
+            cell_overflow: CellOverflow::Saturating,
+            ..BfConfig::default()
+        },
+    )?;
+    interpreter.run_to_end()?;
+    assert_eq!(interpreter.head_value(), 255);
+    Ok(())
+}
+
+#[test]
+fn test_optimized_matches_unoptimized_cell_overflow_error() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        "-",
+        input,
+        &mut output,
+        BfConfig {
+            cell_overflow: CellOverflow::Error,
+            optimize: true,
+            ..BfConfig::default()
+        },
+    )?;
+    let err = interpreter
+        .run_to_end()
+        .expect_err("must occur cell overflow");
+    assert!(matches!(err, BfError::CellOverflow));
+    Ok(())
+}
+
+#[test]
+fn test_optimized_matches_unoptimized_bounded_tape() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(
+        ">>",
+        input,
+        &mut output,
+        BfConfig {
+            optimize: true,
+            tape_config: TapeConfig {
+                size: Some(2),
+                pointer_policy: TapePointerPolicy::Bounded,
+            },
+            ..BfConfig::default()
+        },
+    )?;
+    let err = interpreter
+        .run_to_end()
+        .expect_err("must occur seek over rightmost");
+    assert!(matches!(err, BfError::SeekOverRightmost));
+    Ok(())
+}
+
+#[test]
+fn test_optimized_folds_moves_on_wrapping_tape() -> anyhow::Result<()> {
+    // A fixed-size wrapping tape is the one case where folding a `>`/`<`
+    // run is safe, since modular arithmetic is associative.
+    let source = ">><";
+    let config = BfConfig {
+        optimize: true,
+        tape_config: TapeConfig {
+            size: Some(2),
+            pointer_policy: TapePointerPolicy::Wrapping,
+        },
+        ..BfConfig::default()
+    };
+
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::with_config(source, input, &mut output, config)?;
+    interpreter.run_to_end()?;
+    assert_eq!(interpreter.tape_pointer(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_zero_sized_tape_is_rejected() {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let res = BfInterpreter::with_config(
+        "+",
+        input,
+        &mut output,
+        BfConfig {
+            tape_config: TapeConfig {
+                size: Some(0),
+                ..TapeConfig::default()
+            },
+            ..BfConfig::default()
+        },
+    );
+    let err = res.expect_err("must reject a zero-sized tape");
+    assert!(matches!(err, BfError::EmptyTape));
+}
+
+#[test]
+fn test_run_until_breakpoint_stops_and_resumes() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::new("+.#+.#+.", input, &mut output)?;
+
+    let first = interpreter.run_until_breakpoint()?;
+    let second = interpreter.run_until_breakpoint()?;
+    let third = interpreter.run_until_breakpoint()?;
+
+    assert_eq!(first, DebugStop::Breakpoint);
+    assert_eq!(second, DebugStop::Breakpoint);
+    assert_eq!(third, DebugStop::End);
+    assert_eq!(output, [1, 2, 3]);
+    Ok(())
+}
+
+#[test]
+fn test_run_until_breakpoint_runs_to_end_without_breakpoints() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::new("+++.", input, &mut output)?;
+    assert_eq!(interpreter.run_until_breakpoint()?, DebugStop::End);
+    assert_eq!(output, [3]);
+    Ok(())
+}
+
+#[test]
+fn test_tape_window_clamps_at_bounds() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let interpreter = BfInterpreter::new("", input, &mut output)?;
+    let (tape, pointer) = interpreter.tape_window(3);
+    assert_eq!(tape, &[0]);
+    assert_eq!(pointer, 0);
+    Ok(())
+}
+
+#[test]
+fn test_instruction_window_centers_on_instruction_pointer() -> anyhow::Result<()> {
+    let input = std::io::BufReader::new(&[][..]);
+    let mut output = vec![];
+    let mut interpreter = BfInterpreter::new("+>+>+", input, &mut output)?;
+    interpreter.step()?;
+    interpreter.step()?;
+    let (window, ip) = interpreter.instruction_window(1);
+    assert_eq!(
+        window,
+        &[
+            BfInstruction::GoRight,
+            BfInstruction::Increment,
+            BfInstruction::GoRight
+        ]
+    );
+    assert_eq!(ip, 1);
+    Ok(())
+}
@@ -1,11 +1,23 @@
 use std::path::Path;
 
-use anyhow::{Context as _, bail};
-use bf_interpreter::BfInterpreter;
+use anyhow::Context as _;
+use bf_interpreter::{BfInterpreter, DebugStop};
+
+mod repl;
 
 fn main() -> anyhow::Result<()> {
-    let Some(source_path) = std::env::args().nth(1) else {
-        bail!("expected source file path as a commandline argument");
+    let mut args = std::env::args().skip(1);
+    let mut source_path = None;
+    let mut debug = false;
+    for arg in args.by_ref() {
+        if arg == "--debug" {
+            debug = true;
+        } else {
+            source_path = Some(arg);
+        }
+    }
+    let Some(source_path) = source_path else {
+        return repl::run();
     };
     let source_path = Path::new(&source_path);
     let source = std::fs::read_to_string(source_path).context("source read failed")?;
@@ -13,7 +25,18 @@ fn main() -> anyhow::Result<()> {
     let stdin = std::io::stdin();
     let stdin = stdin.lock();
     let stdout = std::io::stdout();
-    let interpreter = BfInterpreter::new(&source, stdin, stdout)?;
-    interpreter.execute().context("execution failure")?;
-    Ok(())
+    let mut interpreter = BfInterpreter::new(&source, stdin, stdout)?;
+    if debug {
+        while let DebugStop::Breakpoint = interpreter.run_until_breakpoint()? {
+            let (tape, pointer) = interpreter.tape_window(4);
+            let (instructions, ip) = interpreter.instruction_window(4);
+            eprintln!(
+                "breakpoint hit: ip={} instructions={instructions:?} (current={ip}) tape={tape:?} (current={pointer})",
+                interpreter.instruction_pointer()
+            );
+        }
+        Ok(())
+    } else {
+        interpreter.execute().context("execution failure")
+    }
 }
@@ -0,0 +1,335 @@
+use std::io::{self, BufRead, Read, Write};
+
+use bf_interpreter::{BfConfig, BfError, BfInterpreter};
+
+/// Runs an interactive prompt that feeds each line of input into the same
+/// [`BfInterpreter`], so the tape built up by one snippet is still there
+/// for the next one.
+pub fn run() -> anyhow::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut interpreter = BfInterpreter::with_config(
+        "",
+        SnippetInput::new(stdin.lock()),
+        stdout.lock(),
+        BfConfig::default(),
+    )?;
+
+    println!("bf-interpreter REPL. Commands: :tape, :reset, :quit. Anything else is run as Brainfuck source.");
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+
+        let Some(line) = next_command(interpreter.input_mut())? else {
+            break;
+        };
+
+        match line.as_str() {
+            ":quit" | ":exit" => break,
+            ":reset" => {
+                interpreter.reset();
+                println!("(tape reset)");
+            }
+            ":tape" => {
+                println!(
+                    "{:?} (pointer at {})",
+                    interpreter.tape(),
+                    interpreter.tape_pointer()
+                );
+            }
+            source => {
+                match run_snippet(&mut interpreter, source) {
+                    SnippetOutcome::ParseError(err) => {
+                        println!("parse error: {err}");
+                        continue;
+                    }
+                    SnippetOutcome::RuntimeError(err) => println!("runtime error: {err}"),
+                    SnippetOutcome::Ok => {}
+                }
+                io::stdout().flush()?;
+                println!();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// What came of running one REPL snippet through [`run_snippet`].
+#[derive(Debug)]
+enum SnippetOutcome {
+    Ok,
+    ParseError(BfError),
+    RuntimeError(BfError),
+}
+
+/// Appends `source` to `interpreter` and runs it to completion, then
+/// discards the unread remainder of the input line a `,` in it consumed
+/// from, if any, so it can't be misread as the next prompt's source.
+fn run_snippet<R: BufRead, O: Write>(
+    interpreter: &mut BfInterpreter<SnippetInput<R>, O>,
+    source: &str,
+) -> SnippetOutcome {
+    if let Err(err) = interpreter.append_source(source) {
+        return SnippetOutcome::ParseError(err);
+    }
+    let consumed_before = interpreter.input_mut().bytes_consumed();
+    let result = interpreter.run_to_end();
+    let input = interpreter.input_mut();
+    if input.bytes_consumed() != consumed_before && !input.last_byte_was_newline() {
+        input.discard_rest_of_line();
+    }
+    match result {
+        Ok(()) => SnippetOutcome::Ok,
+        Err(err) => SnippetOutcome::RuntimeError(err),
+    }
+}
+
+/// Wraps a [`BufRead`] so the REPL can tell whether a snippet's `,`
+/// instructions consumed any input, and if so discard the rest of the
+/// line they read from, instead of letting [`BufRead::read_line`] read it
+/// back out as the next prompt's source.
+///
+/// `,` and `read_line` would otherwise share the same underlying buffer:
+/// if a line typed in answer to a `,` has bytes left over after the
+/// program stops reading, those bytes sit in the buffer and get handed to
+/// the very next `read_line` call as if the user had typed them as a
+/// command. Only the rest of that one line is discarded; any further
+/// lines already buffered behind it are left alone.
+struct SnippetInput<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+    bytes_consumed: u64,
+    last_byte_was_newline: bool,
+}
+
+impl<R: BufRead> SnippetInput<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+            bytes_consumed: 0,
+            last_byte_was_newline: false,
+        }
+    }
+
+    /// Total bytes read out of this wrapper so far, so the REPL can tell
+    /// whether a snippet's run consumed any input without having to guess
+    /// from its source text.
+    fn bytes_consumed(&self) -> u64 {
+        self.bytes_consumed
+    }
+
+    /// Whether the most recently consumed byte was a newline, so the REPL
+    /// can tell whether a snippet's `,` reads stopped exactly at the end of
+    /// a line, as opposed to partway through one. Buffered input can hold
+    /// several already-typed lines at once (a whole piped script, for
+    /// instance), so "some bytes were consumed" alone doesn't mean anything
+    /// is left over to discard — and a run can consume a newline and then
+    /// keep going partway into the next line, so "a newline was consumed at
+    /// some point" doesn't mean the run stopped cleanly either. Only
+    /// stopping anywhere other than right after a newline leaves a
+    /// remainder behind.
+    fn last_byte_was_newline(&self) -> bool {
+        self.last_byte_was_newline
+    }
+
+    /// Discards already-buffered bytes up to and including the next
+    /// newline, so the unread remainder of a line a `,` was reading from
+    /// can't be misread as the next prompt's source text.
+    ///
+    /// Only inspects what's already in `buf`; it deliberately never calls
+    /// `fill_buf` to fetch more, since that would block on (and then
+    /// consume) whatever the user types *next* whenever a `,` happened to
+    /// consume exactly up to the end of the currently buffered chunk. In
+    /// that case there is no known leftover to discard, so this is a
+    /// no-op, and the bytes of the next line remain genuinely unread.
+    fn discard_rest_of_line(&mut self) {
+        let remaining = &self.buf[self.pos..];
+        match remaining.iter().position(|&b| b == b'\n') {
+            Some(newline_at) => self.consume(newline_at + 1),
+            None => self.consume(remaining.len()),
+        }
+    }
+}
+
+impl<R: BufRead> Read for SnippetInput<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for SnippetInput<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.buf.len() {
+            self.buf.clear();
+            self.pos = 0;
+            let inner_buf = self.inner.fill_buf()?;
+            self.buf.extend_from_slice(inner_buf);
+            let len = inner_buf.len();
+            self.inner.consume(len);
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt > 0 {
+            self.last_byte_was_newline = self.buf[self.pos + amt - 1] == b'\n';
+        }
+        self.pos += amt;
+        self.bytes_consumed += amt as u64;
+    }
+}
+
+/// Reads the next REPL command line the same way [`run`]'s loop does:
+/// one line, newline stripped, `None` on EOF.
+fn next_command<R: BufRead>(input: &mut SnippetInput<R>) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// A `BufRead` that only ever hands back one byte per `fill_buf` call,
+    /// no matter how much input remains, standing in for a pipe whose
+    /// individual reads don't line up with the REPL's notion of a line.
+    struct OneByteAtATime<'a>(&'a [u8]);
+
+    impl Read for OneByteAtATime<'_> {
+        fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+            let buf = self.fill_buf()?;
+            let n = buf.len().min(out.len());
+            out[..n].copy_from_slice(&buf[..n]);
+            self.consume(n);
+            Ok(n)
+        }
+    }
+
+    impl BufRead for OneByteAtATime<'_> {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            Ok(&self.0[..self.0.len().min(1)])
+        }
+
+        fn consume(&mut self, amt: usize) {
+            self.0 = &self.0[amt..];
+        }
+    }
+
+    #[test]
+    fn test_leftover_bytes_on_same_line_are_discarded() -> anyhow::Result<()> {
+        // ",." reads the 'X' its answer line supplies; "+.\n" is the rest
+        // of that same line, delivered in the same chunk, and must not
+        // surface as the next command.
+        let input = SnippetInput::new(Cursor::new(b",.\nX+.\n".as_slice()));
+        let mut output = vec![];
+        let mut interpreter =
+            BfInterpreter::with_config("", input, &mut output, BfConfig::default())?;
+
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+
+        assert_eq!(next_command(interpreter.input_mut())?, None);
+        assert_eq!(output, b"X");
+        Ok(())
+    }
+
+    #[test]
+    fn test_consuming_exactly_to_a_chunk_boundary_does_not_eat_the_next_line() -> anyhow::Result<()>
+    {
+        // With input delivered one byte at a time, a `,` can consume
+        // exactly the single byte that happens to be buffered, leaving
+        // nothing behind. That must not be mistaken for "there's a
+        // leftover remainder", which would otherwise force a fresh,
+        // blocking read that swallows the next line whole.
+        let input = SnippetInput::new(OneByteAtATime(b",.\nA\n+++.\n"));
+        let mut output = vec![];
+        let mut interpreter =
+            BfInterpreter::with_config("", input, &mut output, BfConfig::default())?;
+
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+
+        // The bare newline left over from the answer line surfaces as an
+        // empty command, but the real next command survives intact.
+        assert_eq!(next_command(interpreter.input_mut())?, Some(String::new()));
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert_eq!(source, "+++.");
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+        // The tape carries over between commands, so this lands on 'A' + 3.
+        assert_eq!(interpreter.head_value(), b'A' + 3);
+        assert_eq!(output, b"AD");
+        Ok(())
+    }
+
+    #[test]
+    fn test_blank_answer_line_does_not_swallow_the_next_command() -> anyhow::Result<()> {
+        // Piped input normally arrives in one chunk holding several already
+        // typed lines at once. Here "," reads only the bare newline that
+        // terminates the blank answer line, leaving the rest of the chunk
+        // (the next command, already buffered) untouched: that remainder
+        // must not be mistaken for an unfinished line and discarded.
+        let input = SnippetInput::new(Cursor::new(b",.\n\n+++.\n".as_slice()));
+        let mut output = vec![];
+        let mut interpreter =
+            BfInterpreter::with_config("", input, &mut output, BfConfig::default())?;
+
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert_eq!(source, "+++.");
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+        assert_eq!(interpreter.head_value(), b'\n' + 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reads_crossing_into_the_next_line_still_discard_their_remainder() -> anyhow::Result<()>
+    {
+        // ",,,,." reads four bytes: "A", "B", the newline ending the answer
+        // line, and then "C" from the start of the *next* line. Consuming a
+        // newline along the way doesn't mean the run stopped there — it
+        // stopped partway into "CD", so "D\n" is still a genuine leftover
+        // that must be discarded before the next command is read.
+        let input = SnippetInput::new(Cursor::new(b",,,,.\nAB\nCD\n".as_slice()));
+        let mut output = vec![];
+        let mut interpreter =
+            BfInterpreter::with_config("", input, &mut output, BfConfig::default())?;
+
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+
+        assert_eq!(next_command(interpreter.input_mut())?, None);
+        assert_eq!(output, b"C");
+        Ok(())
+    }
+
+    #[test]
+    fn test_future_commands_are_untouched_when_no_input_is_consumed() -> anyhow::Result<()> {
+        let input = SnippetInput::new(Cursor::new(b"+++.\n++.\n".as_slice()));
+        let mut output = vec![];
+        let mut interpreter =
+            BfInterpreter::with_config("", input, &mut output, BfConfig::default())?;
+
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+
+        let source = next_command(interpreter.input_mut())?.unwrap();
+        assert_eq!(source, "++.");
+        assert!(matches!(run_snippet(&mut interpreter, &source), SnippetOutcome::Ok));
+        assert_eq!(output, [3, 5]);
+        Ok(())
+    }
+}